@@ -0,0 +1,44 @@
+//! Criterion benchmark for `DBSCAN::cluster`
+//!
+//! Runs clustering over Gaussian-blob datasets of increasing size, with
+//! both the default brute-force neighbour search and the `KdTreeIndex`
+//! from `with_index`, so regressions in either path - or the speedup the
+//! k-d tree is supposed to provide over brute force - show up as a
+//! measurable change, rather than something only noticed once it's slow
+//! in production.
+//!
+//! Run with `cargo bench --features blobs`.
+
+extern crate criterion;
+extern crate dbscan;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use dbscan::{generate_blobs, Algorithm, DBScanParams, KdTreeIndex, DBSCAN};
+
+const CENTROIDS: &[(f64, f64)] = &[(0.0, 0.0), (10.0, 10.0), (-10.0, 10.0)];
+const SIZES: &[usize] = &[10, 100, 1_000, 10_000];
+
+fn bench_cluster(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dbscan_cluster");
+
+    for &size in SIZES {
+        let points = generate_blobs(size, CENTROIDS, 1.5, 42);
+
+        let params = DBScanParams::new(2f64, 4).expect("invalid epsilon/min_pts");
+        let brute_force = DBSCAN::new(params);
+        group.bench_with_input(BenchmarkId::new("brute_force", size), &points, |b, points| {
+            b.iter(|| brute_force.cluster(points));
+        });
+
+        let params = DBScanParams::new(2f64, 4).expect("invalid epsilon/min_pts");
+        let kd_tree = DBSCAN::with_index(params, |pts| Box::new(KdTreeIndex::new(pts)));
+        group.bench_with_input(BenchmarkId::new("kd_tree", size), &points, |b, points| {
+            b.iter(|| kd_tree.cluster(points));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_cluster);
+criterion_main!(benches);
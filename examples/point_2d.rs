@@ -7,7 +7,7 @@
 //! to cluster them accordingly and print out the clusters
 
 extern crate dbscan;
-use dbscan::{Algorithm, Proximity, DBSCAN};
+use dbscan::{Algorithm, DBScanParams, Proximity, DBSCAN};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
@@ -57,7 +57,7 @@ fn main() {
     (0f64, 0f64), // Cluster
     (1., 0.),     //
     (0., -1.),    // -------
-    (1., 2.),     // Noise
+    (1., 2.),     // Cluster (singleton)
     (3., 5.),     // Cluster
     (4., 5.),     //
     (5., 5.),     // -------
@@ -80,8 +80,10 @@ fn main() {
   // Create a new instance of the algorithm
   //
   // This instance will consider all points within a radius of 2 units as
-  // 'neighours' and any point with more than 1 neighbour forms a cluster.
-  let alg = DBSCAN::new(2f64, 1);
+  // 'neighbours' and any point with at least 2 points (itself plus one
+  // neighbour) in its neighbourhood forms a cluster.
+  let params = DBScanParams::new(2f64, 2).expect("invalid epsilon/min_pts");
+  let alg = DBSCAN::new(params);
 
   // Print out clusters
   //
@@ -0,0 +1,53 @@
+//! Core traits shared by the clustering algorithms in this crate
+
+/// Types that can report a distance to another instance of themselves.
+///
+/// Implementing this trait, along with `Hash`, `Eq` and `Copy`, allows a
+/// type to be used as a clusterable point by the algorithms in this crate.
+pub trait Proximity {
+    /// The type used to represent the distance between two points
+    type Output: PartialOrd + Copy;
+
+    /// Computes the distance between this point and `other`
+    fn distance(&self, other: &Self) -> Self::Output;
+
+    /// Returns `true` if `other` lies within `epsilon` of this point
+    fn is_near(&self, other: &Self, epsilon: Self::Output) -> bool {
+        self.distance(other) <= epsilon
+    }
+}
+
+/// A clustering algorithm that can be run over a slice of clusterable points
+pub trait Algorithm<T> {
+    /// Runs the algorithm over `clusterables` and returns the results
+    fn cluster(&self, clusterables: &[T]) -> Box<dyn Clustered<T>>;
+}
+
+/// The outcome of running a clustering algorithm
+pub trait Clustered<T> {
+    /// Returns the discovered clusters
+    fn clusters(&self) -> Vec<Vec<T>>;
+
+    /// Returns the points that were classified as noise
+    fn noise(&self) -> Vec<T>;
+
+    /// Returns the points that were classified as core points: points whose
+    /// own neighbourhood was dense enough to drive cluster expansion
+    fn core_points(&self) -> Vec<T>;
+
+    /// Returns the points that were classified as border points: points
+    /// reachable from a core point, but not themselves core
+    fn border_points(&self) -> Vec<T>;
+}
+
+/// Distinguishes the role a point played while it was being clustered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointKind {
+    /// A point whose epsilon-neighbourhood, including itself, contains at
+    /// least `min_pts` points
+    Core,
+    /// A point that is part of a cluster but is not itself a core point
+    Border,
+    /// A point that is not part of any cluster
+    Noise,
+}
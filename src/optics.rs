@@ -0,0 +1,297 @@
+//! OPTICS: Ordering Points To Identify the Clustering Structure
+//!
+//! Unlike DBSCAN, OPTICS does not assign clusters directly. It instead
+//! walks the dataset once, producing an ordering of the points together
+//! with a reachability-distance for each one. Cutting the resulting
+//! reachability plot at different thresholds recovers DBSCAN-equivalent
+//! clusterings without re-scanning the data, which makes OPTICS a better
+//! fit than DBSCAN for datasets containing clusters of varying density.
+
+use crate::{neighbours, Algorithm, Clustered, PointKind, Proximity, Results};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Represents the OPTICS algorithm
+pub struct OPTICS<T>
+where
+    T: Proximity + Eq + Copy + std::hash::Hash,
+{
+    epsilon: <T as Proximity>::Output,
+    min_pts: usize,
+}
+
+impl<T> OPTICS<T>
+where
+    T: Proximity + Eq + Copy + std::hash::Hash,
+{
+    /// Initialize the algorithm
+    ///
+    /// - `epsilon` This parameter bounds the neighbourhood searched around
+    /// each point
+    /// - `min_pts` A point's core-distance is only defined once its
+    /// epsilon-neighbourhood, including the point itself, contains at
+    /// least `min_pts` points (see `core_distance`)
+    ///
+    /// Panics if `min_pts` is zero: every neighbourhood would trivially
+    /// satisfy it, and `core_distance` has no `min_pts`-th nearest
+    /// neighbour to report in that case.
+    pub fn new(epsilon: <T as Proximity>::Output, min_pts: usize) -> OPTICS<T> {
+        assert!(min_pts > 0, "min_pts must be at least 1");
+        OPTICS { epsilon, min_pts }
+    }
+}
+
+impl<T> Algorithm<T> for OPTICS<T>
+where
+    T: Proximity + Eq + Copy + std::hash::Hash + 'static,
+{
+    fn cluster(&self, clusterables: &[T]) -> Box<dyn Clustered<T>> {
+        Box::new(Reachability {
+            ordering: optics(clusterables, self.epsilon, self.min_pts),
+            epsilon: self.epsilon,
+        })
+    }
+}
+
+/// Holds the results of the OPTICS algorithm
+///
+/// This is the processing order the algorithm produced, together with each
+/// point's reachability-distance and core-distance. A point's
+/// reachability-distance is `None` when it starts a new density-reachable
+/// run, i.e. there is no meaningful distance linking it to the point
+/// processed before it.
+pub struct Reachability<T>
+where
+    T: Proximity,
+{
+    ordering: Vec<(T, Option<<T as Proximity>::Output>, Option<<T as Proximity>::Output>)>,
+    epsilon: <T as Proximity>::Output,
+}
+
+impl<T> Reachability<T>
+where
+    T: Proximity + Eq + Copy + std::hash::Hash,
+{
+    /// Returns the points in the order produced by the algorithm, paired
+    /// with their reachability-distance
+    pub fn ordering(&self) -> Vec<(T, Option<<T as Proximity>::Output>)> {
+        self.ordering
+            .iter()
+            .map(|(point, reachability, _)| (*point, *reachability))
+            .collect()
+    }
+
+    /// Recovers a DBSCAN-equivalent clustering by cutting the reachability
+    /// plot at `threshold`
+    ///
+    /// A point starts a new cluster when its reachability-distance is
+    /// undefined or exceeds `threshold`, provided its own core-distance is
+    /// within `threshold`; every other point with a reachability-distance
+    /// beyond `threshold` is noise. A point's `PointKind` is `Core`
+    /// whenever its own core-distance is within `threshold`, regardless of
+    /// where it falls in the ordering.
+    pub fn extract_clusters(&self, threshold: <T as Proximity>::Output) -> Results<T> {
+        let mut labels = HashMap::new();
+        let mut cluster_count = -1i32;
+        let mut in_cluster = false;
+
+        for (point, reachability, core_distance) in &self.ordering {
+            let starts_new_run = match reachability {
+                Some(reachability) => *reachability > threshold,
+                None => true,
+            };
+
+            if starts_new_run {
+                in_cluster = match core_distance {
+                    Some(core_distance) if *core_distance <= threshold => {
+                        cluster_count += 1;
+                        true
+                    }
+                    _ => false,
+                };
+            }
+
+            let is_core = matches!(core_distance, Some(core_distance) if *core_distance <= threshold);
+            let kind = match (is_core, in_cluster) {
+                (true, _) => PointKind::Core,
+                (false, true) => PointKind::Border,
+                (false, false) => PointKind::Noise,
+            };
+
+            let label = if in_cluster { Some(cluster_count) } else { Some(-1) };
+            labels.insert(*point, (label, kind));
+        }
+
+        Results::new(labels)
+    }
+}
+
+impl<T> Clustered<T> for Reachability<T>
+where
+    T: Proximity + Eq + Copy + std::hash::Hash,
+{
+    fn clusters(&self) -> Vec<Vec<T>> {
+        self.extract_clusters(self.epsilon).clusters()
+    }
+
+    fn noise(&self) -> Vec<T> {
+        self.extract_clusters(self.epsilon).noise()
+    }
+
+    fn core_points(&self) -> Vec<T> {
+        self.extract_clusters(self.epsilon).core_points()
+    }
+
+    fn border_points(&self) -> Vec<T> {
+        self.extract_clusters(self.epsilon).border_points()
+    }
+}
+
+// A `(distance, point)` pair ordered smallest-distance-first, so it can be
+// used as the key in a `BinaryHeap`-backed min-priority-queue. `Output` is
+// only `PartialOrd` (it is `f64` in the common case), so this wraps the
+// comparison rather than requiring a full `Ord` bound on `Proximity`.
+struct MinScored<O, T>(O, T);
+
+impl<O: PartialOrd, T> PartialEq for MinScored<O, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+impl<O: PartialOrd, T> Eq for MinScored<O, T> {}
+
+impl<O: PartialOrd, T> PartialOrd for MinScored<O, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+}
+
+impl<O: PartialOrd, T> Ord for MinScored<O, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+}
+
+// Computes the core-distance of `point`: the distance to the `min_pts`-th
+// nearest point in its epsilon-neighbourhood, counting `point` itself, or
+// `None` if that neighbourhood has fewer than `min_pts` points. This is the
+// same convention `is_core` uses in lib.rs, so a point's core-distance is
+// defined exactly when it is a DBSCAN core point with the same `min_pts`.
+fn core_distance<T>(point: T, nbrs: &[T], min_pts: usize) -> Option<<T as Proximity>::Output>
+where
+    T: Proximity + Copy,
+{
+    if nbrs.len() + 1 < min_pts {
+        return None;
+    }
+
+    if min_pts <= 1 {
+        // `point` itself is already the `min_pts`-th (1st) nearest member
+        // of its own neighbourhood.
+        return Some(point.distance(&point));
+    }
+
+    let mut distances = nbrs.iter().map(|nbr| point.distance(nbr)).collect::<Vec<_>>();
+    distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // `point` itself occupies rank 1, so the `min_pts`-th nearest member of
+    // the neighbourhood is the `(min_pts - 1)`-th entry of `nbrs`.
+    Some(distances[min_pts - 2])
+}
+
+// Expands the seed queue with `point`'s unprocessed neighbours, inserting or
+// decreasing each neighbour's reachability-distance key
+fn update_seeds<T>(
+    point: T,
+    core_distance: <T as Proximity>::Output,
+    nbrs: &[T],
+    processed: &HashSet<T>,
+    seeds: &mut BinaryHeap<MinScored<<T as Proximity>::Output, T>>,
+    best: &mut HashMap<T, <T as Proximity>::Output>,
+) where
+    T: Proximity + Eq + Copy + std::hash::Hash,
+{
+    for &nbr in nbrs {
+        if processed.contains(&nbr) {
+            continue;
+        }
+
+        let distance_to_point = point.distance(&nbr);
+        let reachability_distance = if core_distance > distance_to_point {
+            core_distance
+        } else {
+            distance_to_point
+        };
+
+        let is_improvement = match best.get(&nbr) {
+            Some(&existing) => reachability_distance < existing,
+            None => true,
+        };
+
+        if is_improvement {
+            best.insert(nbr, reachability_distance);
+            seeds.push(MinScored(reachability_distance, nbr));
+        }
+    }
+}
+
+// Runs the OPTICS algorithm, returning the processing order together with
+// each point's reachability-distance and core-distance
+fn optics<T>(
+    clusterables: &[T],
+    epsilon: <T as Proximity>::Output,
+    min_pts: usize,
+) -> Vec<(T, Option<<T as Proximity>::Output>, Option<<T as Proximity>::Output>)>
+where
+    T: Proximity + Eq + Copy + std::hash::Hash,
+{
+    let mut processed = HashSet::new();
+    let mut ordering = Vec::with_capacity(clusterables.len());
+
+    for &point in clusterables {
+        if processed.contains(&point) {
+            continue;
+        }
+
+        let nbrs = neighbours(point, clusterables, epsilon);
+        let point_core_distance = core_distance(point, &nbrs, min_pts);
+
+        processed.insert(point);
+        ordering.push((point, None, point_core_distance));
+
+        let point_core_distance = match point_core_distance {
+            Some(point_core_distance) => point_core_distance,
+            None => continue,
+        };
+
+        let mut seeds = BinaryHeap::new();
+        let mut best = HashMap::new();
+        update_seeds(point, point_core_distance, &nbrs, &processed, &mut seeds, &mut best);
+
+        while let Some(MinScored(reachability_distance, current)) = seeds.pop() {
+            if processed.contains(&current) {
+                continue;
+            }
+
+            let current_nbrs = neighbours(current, clusterables, epsilon);
+            let current_core_distance = core_distance(current, &current_nbrs, min_pts);
+
+            processed.insert(current);
+            ordering.push((current, Some(reachability_distance), current_core_distance));
+
+            if let Some(current_core_distance) = current_core_distance {
+                update_seeds(
+                    current,
+                    current_core_distance,
+                    &current_nbrs,
+                    &processed,
+                    &mut seeds,
+                    &mut best,
+                );
+            }
+        }
+    }
+
+    ordering
+}
@@ -0,0 +1,190 @@
+//! Spatial indexes used to accelerate neighbourhood queries
+//!
+//! `DBSCAN::cluster` repeatedly asks "which points lie within `epsilon` of
+//! this point?". Answering that with a linear scan, as the crate used to,
+//! makes clustering O(n^2) overall. A `SpatialIndex` answers the same
+//! question, but lets the clusterable type opt into a faster strategy.
+
+use crate::{neighbours, Proximity};
+
+/// Answers epsilon-neighbourhood queries over a fixed set of points
+///
+/// A `DBSCAN` instance builds its index once, up front, and calls
+/// `range_query` for every point instead of re-scanning the full dataset.
+pub trait SpatialIndex<T>
+where
+    T: Proximity + Eq + Copy,
+{
+    /// Returns every indexed point, other than `point` itself, within
+    /// `epsilon` of `point`
+    fn range_query(&self, point: &T, epsilon: <T as Proximity>::Output) -> Vec<T>;
+}
+
+/// A `SpatialIndex` that scans every point for each query
+///
+/// This works for any `Proximity` implementation, since it only relies on
+/// `distance`, at the cost of the O(n) per-query scan an index exists to
+/// avoid.
+pub struct BruteForceIndex<T> {
+    points: Vec<T>,
+}
+
+impl<T> BruteForceIndex<T>
+where
+    T: Proximity + Eq + Copy,
+{
+    /// Builds the index over `points`
+    pub fn new(points: &[T]) -> BruteForceIndex<T> {
+        BruteForceIndex {
+            points: points.to_vec(),
+        }
+    }
+}
+
+impl<T> SpatialIndex<T> for BruteForceIndex<T>
+where
+    T: Proximity + Eq + Copy,
+{
+    fn range_query(&self, point: &T, epsilon: <T as Proximity>::Output) -> Vec<T> {
+        neighbours(*point, &self.points, epsilon)
+    }
+}
+
+/// Types that can expose their position as fixed-dimension Euclidean
+/// coordinates
+///
+/// Implementing this trait, in addition to `Proximity`, lets a type opt
+/// into `KdTreeIndex` acceleration. Types without a natural coordinate
+/// representation, or whose `distance` isn't a Euclidean metric, simply
+/// don't implement it and fall back to `BruteForceIndex`.
+pub trait Coordinates {
+    /// Returns this point's position, one entry per dimension
+    fn coordinates(&self) -> &[f64];
+}
+
+// A node in the k-d tree: the point it holds and its left/right subtrees,
+// split on the axis `depth % dimensions` of the tree that owns it
+struct Node<T> {
+    point: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+fn build<T>(points: &mut [T], depth: usize, dimensions: usize) -> Option<Box<Node<T>>>
+where
+    T: Coordinates + Copy,
+{
+    if points.is_empty() {
+        return None;
+    }
+
+    if dimensions == 0 {
+        // A `Coordinates` impl is free to report zero dimensions (an empty
+        // coordinate slice); there's no axis to split on, so fall back to
+        // an unordered chain that `search` walks linearly instead of
+        // indexing `depth % dimensions`.
+        let (first, rest) = points.split_at_mut(1);
+        return Some(Box::new(Node {
+            point: first[0],
+            left: build(rest, depth, dimensions),
+            right: None,
+        }));
+    }
+
+    let axis = depth % dimensions;
+    points.sort_by(|a, b| a.coordinates()[axis].partial_cmp(&b.coordinates()[axis]).unwrap());
+
+    let median = points.len() / 2;
+    let point = points[median];
+    let (left_points, rest) = points.split_at_mut(median);
+    let right_points = &mut rest[1..];
+
+    Some(Box::new(Node {
+        point,
+        left: build(left_points, depth + 1, dimensions),
+        right: build(right_points, depth + 1, dimensions),
+    }))
+}
+
+fn search<T>(
+    node: &Option<Box<Node<T>>>,
+    point: &T,
+    epsilon: f64,
+    depth: usize,
+    dimensions: usize,
+    found: &mut Vec<T>,
+) where
+    T: Proximity<Output = f64> + Coordinates + Eq + Copy,
+{
+    let node = match node {
+        Some(node) => node,
+        None => return,
+    };
+
+    if *point != node.point && point.is_near(&node.point, epsilon) {
+        found.push(node.point);
+    }
+
+    if dimensions == 0 {
+        // Mirrors `build`'s fallback: no axis to prune on, so walk the
+        // whole chain.
+        search(&node.left, point, epsilon, depth, dimensions, found);
+        search(&node.right, point, epsilon, depth, dimensions, found);
+        return;
+    }
+
+    let axis = depth % dimensions;
+    let axis_distance = point.coordinates()[axis] - node.point.coordinates()[axis];
+    let (near, far) = if axis_distance <= 0.0 {
+        (&node.left, &node.right)
+    } else {
+        (&node.right, &node.left)
+    };
+
+    search(near, point, epsilon, depth + 1, dimensions, found);
+
+    // Only the far subtree can be pruned: its splitting plane is at least
+    // `axis_distance` away along `axis`, so it holds no point within
+    // `epsilon` unless that plane itself is within `epsilon`.
+    if axis_distance.abs() <= epsilon {
+        search(far, point, epsilon, depth + 1, dimensions, found);
+    }
+}
+
+/// A k-d tree index for clusterable types with Euclidean coordinates
+///
+/// This accelerates `range_query` for the common case where
+/// `Proximity::Output` is a Euclidean distance over fixed-dimension
+/// coordinates, by pruning subtrees that cannot contain a point within
+/// `epsilon`.
+pub struct KdTreeIndex<T> {
+    root: Option<Box<Node<T>>>,
+    dimensions: usize,
+}
+
+impl<T> KdTreeIndex<T>
+where
+    T: Coordinates + Copy,
+{
+    /// Builds the index over `points`
+    pub fn new(points: &[T]) -> KdTreeIndex<T> {
+        let dimensions = points.first().map_or(0, |point| point.coordinates().len());
+        let mut items = points.to_vec();
+
+        KdTreeIndex {
+            root: build(&mut items, 0, dimensions),
+            dimensions,
+        }
+    }
+}
+
+impl<T> SpatialIndex<T> for KdTreeIndex<T>
+where
+    T: Proximity<Output = f64> + Coordinates + Eq + Copy,
+{
+    fn range_query(&self, point: &T, epsilon: f64) -> Vec<T> {
+        let mut found = Vec::new();
+        search(&self.root, point, epsilon, 0, self.dimensions, &mut found);
+        found
+    }
+}